@@ -0,0 +1,117 @@
+//! Standalone CLI for querying Claude usage and context status outside the XFCE panel
+
+use claude_status_core::{api, credentials, transcript};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(name = "claude-status", version, about = "Query Claude Code usage and context status")]
+struct Cli {
+    /// Emit machine-readable JSON instead of human-readable text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Show five-hour and seven-day usage utilization
+    Usage,
+    /// Show context window usage for the latest transcript
+    Context,
+}
+
+#[derive(Serialize)]
+struct UsageOutput {
+    five_hour_pct: f64,
+    five_hour_resets_at: String,
+    seven_day_pct: f64,
+    seven_day_resets_at: String,
+}
+
+#[derive(Serialize)]
+struct ContextOutput {
+    context_pct: f64,
+    context_tokens: i64,
+    output_tokens: i64,
+    cumulative_tokens: i64,
+    context_window_size: i64,
+    model_name: Option<String>,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Commands::Usage => run_usage(cli.json),
+        Commands::Context => run_context(cli.json),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run_usage(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut creds = credentials::load_credentials(None)?;
+
+    if credentials::is_expired(&creds) {
+        credentials::refresh_access_token(&mut creds, None)?;
+    }
+
+    let usage = api::fetch_usage(&creds.access_token)?;
+
+    if json {
+        let output = UsageOutput {
+            five_hour_pct: usage.five_hour.utilization,
+            five_hour_resets_at: usage.five_hour.resets_at.to_rfc3339(),
+            seven_day_pct: usage.seven_day.utilization,
+            seven_day_resets_at: usage.seven_day.resets_at.to_rfc3339(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!(
+            "5-hour:  {:.1}% (resets {})",
+            usage.five_hour.utilization,
+            usage.five_hour.resets_at.to_rfc3339()
+        );
+        println!(
+            "7-day:   {:.1}% (resets {})",
+            usage.seven_day.utilization,
+            usage.seven_day.resets_at.to_rfc3339()
+        );
+    }
+
+    Ok(())
+}
+
+fn run_context(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let context = transcript::read_context()?;
+
+    if json {
+        let output = ContextOutput {
+            context_pct: context.context_pct,
+            context_tokens: context.context_tokens,
+            output_tokens: context.output_tokens,
+            cumulative_tokens: context.cumulative_tokens,
+            context_window_size: context.context_window_size,
+            model_name: context.model_name,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        let model = context
+            .model_name
+            .as_deref()
+            .map(|m| format!(" [{}]", m))
+            .unwrap_or_default();
+        println!(
+            "context: {:.1}% ({} / {} tokens){}",
+            context.context_pct, context.context_tokens, context.context_window_size, model
+        );
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! API client for Anthropic usage endpoint
 
 use chrono::{DateTime, Utc};
+use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 use thiserror::Error;
 
@@ -42,9 +43,12 @@ const USAGE_API_URL: &str = "https://api.anthropic.com/api/oauth/usage";
 const USER_AGENT: &str = "xfce-claude-status/0.1";
 
 /// Fetch usage data from the Anthropic API
-pub fn fetch_usage(access_token: &str) -> Result<UsageData, ApiError> {
+pub fn fetch_usage(access_token: &SecretString) -> Result<UsageData, ApiError> {
     let response = ureq::get(USAGE_API_URL)
-        .set("Authorization", &format!("Bearer {}", access_token))
+        .set(
+            "Authorization",
+            &format!("Bearer {}", access_token.expose_secret()),
+        )
         .set("anthropic-beta", "oauth-2025-04-20")
         .set("User-Agent", USER_AGENT)
         .call();
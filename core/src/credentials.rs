@@ -1,9 +1,11 @@
 //! Credential loading from Claude Code config files
 
+use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
+use zeroize::Zeroize;
 
 #[derive(Debug, Error)]
 pub enum CredentialsError {
@@ -15,24 +17,42 @@ pub enum CredentialsError {
     MissingOAuth,
     #[error("Missing access token")]
     MissingToken,
+    #[error("No refresh token available")]
+    MissingRefreshToken,
+    #[error("Failed to refresh access token: {0}")]
+    RefreshError(String),
 }
 
+/// OAuth credentials for the Anthropic API
+///
+/// `access_token` and `refresh_token` are wrapped in [`SecretString`] so they
+/// can't be printed via `{:?}` and are only ever unwrapped at the call sites
+/// that need the raw value (`api::fetch_usage`, [`refresh_access_token`]).
 #[derive(Debug, Clone)]
 pub struct Credentials {
-    pub access_token: String,
+    pub access_token: SecretString,
+    pub refresh_token: Option<SecretString>,
+    /// Access token expiry, epoch milliseconds
+    pub expires_at: Option<i64>,
     pub plan_name: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Zeroize)]
+#[zeroize(drop)]
 struct CredentialsFile {
     #[serde(rename = "claudeAiOauth")]
     claude_ai_oauth: Option<OAuthSection>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Zeroize)]
+#[zeroize(drop)]
 struct OAuthSection {
     #[serde(rename = "accessToken")]
     access_token: Option<String>,
+    #[serde(rename = "refreshToken")]
+    refresh_token: Option<String>,
+    #[serde(rename = "expiresAt")]
+    expires_at: Option<i64>,
     #[serde(rename = "subscriptionType")]
     subscription_type: Option<String>,
 }
@@ -40,6 +60,16 @@ struct OAuthSection {
 /// Default credentials file path
 const DEFAULT_CREDS_PATH: &str = ".claude/.credentials.json";
 
+/// OAuth token endpoint used to exchange a refresh token for a new access token
+const OAUTH_TOKEN_URL: &str = "https://console.anthropic.com/v1/oauth/token";
+
+/// OAuth client id used by Claude Code
+const CLAUDE_CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
+
+/// How far ahead of the real expiry we treat the token as expired, to absorb
+/// clock skew and the round-trip time of the usage request that follows
+const EXPIRY_SKEW_MS: i64 = 60_000;
+
 /// Expand ~ to home directory
 fn expand_path(path: &str) -> PathBuf {
     if let Some(rest) = path.strip_prefix("~/") {
@@ -70,17 +100,34 @@ pub fn load_credentials(path: Option<&str>) -> Result<Credentials, CredentialsEr
         None => default_credentials_path(),
     };
 
-    let contents = fs::read_to_string(&path)?;
+    let mut contents = fs::read_to_string(&path)?;
     let file: CredentialsFile = serde_json::from_str(&contents)?;
+    // `serde_json::from_str` copies the token strings into `file`'s own
+    // Strings rather than borrowing, so it's safe to scrub the raw file
+    // contents (which also hold the plaintext token) right away instead of
+    // leaving them to linger in memory until this function returns.
+    contents.zeroize();
 
-    let oauth = file.claude_ai_oauth.ok_or(CredentialsError::MissingOAuth)?;
+    // `CredentialsFile` zeroizes on drop, so its field is taken rather than
+    // moved out
+    let mut file = file;
+    let mut oauth = file.claude_ai_oauth.take().ok_or(CredentialsError::MissingOAuth)?;
 
     let access_token = oauth
         .access_token
+        .take()
         .filter(|t| !t.is_empty())
         .ok_or(CredentialsError::MissingToken)?;
 
-    let plan_name = oauth.subscription_type.and_then(|sub| {
+    let refresh_token = oauth
+        .refresh_token
+        .take()
+        .filter(|t| !t.is_empty())
+        .map(SecretString::from);
+
+    let expires_at = oauth.expires_at.take();
+
+    let plan_name = oauth.subscription_type.take().and_then(|sub| {
         if sub.contains("max") {
             Some("Max".to_string())
         } else if sub.contains("pro") {
@@ -91,11 +138,107 @@ pub fn load_credentials(path: Option<&str>) -> Result<Credentials, CredentialsEr
     });
 
     Ok(Credentials {
-        access_token,
+        access_token: SecretString::from(access_token),
+        refresh_token,
+        expires_at,
         plan_name,
     })
 }
 
+/// Returns true if `creds.expires_at` is in the past or within
+/// [`EXPIRY_SKEW_MS`] of now. Credentials with no known expiry are treated
+/// as not expired, since some `.credentials.json` files predate the field.
+pub fn is_expired(creds: &Credentials) -> bool {
+    match creds.expires_at {
+        Some(expires_at) => chrono::Utc::now().timestamp_millis() + EXPIRY_SKEW_MS >= expires_at,
+        None => false,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: i64,
+}
+
+/// Exchange `creds.refresh_token` for a new access token, updating `creds` in
+/// place and writing the refreshed OAuth section back to `path` (or the
+/// default credentials path) atomically, so Claude Code itself keeps working
+/// off the same file.
+pub fn refresh_access_token(
+    creds: &mut Credentials,
+    path: Option<&str>,
+) -> Result<(), CredentialsError> {
+    let refresh_token = creds
+        .refresh_token
+        .as_ref()
+        .ok_or(CredentialsError::MissingRefreshToken)?;
+
+    let response = ureq::post(OAUTH_TOKEN_URL)
+        .set("anthropic-beta", "oauth-2025-04-20")
+        .send_json(ureq::json!({
+            "grant_type": "refresh_token",
+            "refresh_token": refresh_token.expose_secret(),
+            "client_id": CLAUDE_CLIENT_ID,
+        }))
+        .map_err(|e| CredentialsError::RefreshError(e.to_string()))?;
+
+    let refreshed: RefreshResponse = response
+        .into_json()
+        .map_err(|e| CredentialsError::RefreshError(e.to_string()))?;
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let expires_at = now_ms + refreshed.expires_in * 1000;
+    let new_refresh_token = refreshed
+        .refresh_token
+        .unwrap_or_else(|| refresh_token.expose_secret().to_string());
+
+    let resolved_path = match path {
+        Some(p) => expand_path(p),
+        None => default_credentials_path(),
+    };
+    write_oauth_section_atomic(
+        &resolved_path,
+        &refreshed.access_token,
+        &new_refresh_token,
+        expires_at,
+    )?;
+
+    creds.access_token = SecretString::from(refreshed.access_token);
+    creds.refresh_token = Some(SecretString::from(new_refresh_token));
+    creds.expires_at = Some(expires_at);
+
+    Ok(())
+}
+
+/// Rewrite the `claudeAiOauth` section of the credentials file in place,
+/// preserving any other top-level fields, by writing to a temp file in the
+/// same directory and renaming it over the original.
+fn write_oauth_section_atomic(
+    path: &Path,
+    access_token: &str,
+    refresh_token: &str,
+    expires_at: i64,
+) -> Result<(), CredentialsError> {
+    let mut contents = fs::read_to_string(path)?;
+    let mut root: serde_json::Value = serde_json::from_str(&contents)?;
+    contents.zeroize();
+
+    let oauth = root
+        .get_mut("claudeAiOauth")
+        .ok_or(CredentialsError::MissingOAuth)?;
+    oauth["accessToken"] = serde_json::Value::String(access_token.to_string());
+    oauth["refreshToken"] = serde_json::Value::String(refresh_token.to_string());
+    oauth["expiresAt"] = serde_json::Value::from(expires_at);
+
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string_pretty(&root)?)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +255,37 @@ mod tests {
         let expanded = expand_path("/absolute/path");
         assert_eq!(expanded, PathBuf::from("/absolute/path"));
     }
+
+    fn creds_with_expiry(expires_at: Option<i64>) -> Credentials {
+        Credentials {
+            access_token: SecretString::from("token"),
+            refresh_token: None,
+            expires_at,
+            plan_name: None,
+        }
+    }
+
+    #[test]
+    fn test_is_expired_none_expiry_is_not_expired() {
+        assert!(!is_expired(&creds_with_expiry(None)));
+    }
+
+    #[test]
+    fn test_is_expired_past_expiry() {
+        let past = chrono::Utc::now().timestamp_millis() - 1_000;
+        assert!(is_expired(&creds_with_expiry(Some(past))));
+    }
+
+    #[test]
+    fn test_is_expired_within_skew_window() {
+        // Inside EXPIRY_SKEW_MS of expiring counts as expired already
+        let almost = chrono::Utc::now().timestamp_millis() + EXPIRY_SKEW_MS / 2;
+        assert!(is_expired(&creds_with_expiry(Some(almost))));
+    }
+
+    #[test]
+    fn test_is_expired_well_in_future_is_not_expired() {
+        let future = chrono::Utc::now().timestamp_millis() + EXPIRY_SKEW_MS * 10;
+        assert!(!is_expired(&creds_with_expiry(Some(future))));
+    }
 }
@@ -1,26 +1,63 @@
 //! FFI boundary definitions for C interop
 
 use std::ffi::{CStr, CString};
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_void};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread;
 
 use crate::api::UsageData;
 use crate::config::Config;
 use crate::credentials::Credentials;
-use crate::monitor::CredentialsMonitor;
+use crate::history::{History, Sample};
+use crate::monitor::{CredsChangeCallback, StatusMonitor};
 use crate::transcript::ContextInfo;
 
 /// Opaque handle to the Rust core state
 pub struct ClaudeStatusCore {
     credentials: Option<Credentials>,
+    credentials_path: Option<String>,
     config: Config,
-    monitor: Option<CredentialsMonitor>,
-    last_usage: Option<UsageData>,
+    monitor: Option<StatusMonitor>,
+    last_usage: Arc<Mutex<Option<UsageData>>>,
     last_context: Option<ContextInfo>,
-    creds_changed: Arc<Mutex<bool>>,
+    creds_changed: Arc<AtomicBool>,
+    transcript_changed: Arc<AtomicBool>,
+    /// Set by `claude_status_core_fetch_usage_async` when a background fetch
+    /// has written a new result into `last_usage`
+    usage_ready: Arc<AtomicBool>,
+    history: Option<History>,
+    /// Display string of the error from the last `load_credentials`,
+    /// `fetch_usage` (sync or async), or `read_context` call that returned an
+    /// `Err`; cleared on the next `Ok`. Shared behind a mutex, like
+    /// `last_usage`, so the async fetch worker thread can set it too.
+    last_error: Arc<Mutex<Option<CString>>>,
 }
 
+/// Store `err`'s `Display` output as the last-error string, to be retrieved
+/// via `claude_status_core_last_error`
+fn set_last_error<E: std::fmt::Display>(last_error: &Mutex<Option<CString>>, err: &E) {
+    *last_error.lock().unwrap() = CString::new(err.to_string()).ok();
+}
+
+/// C callback invoked by `claude_status_core_fetch_usage_async` when the
+/// background fetch completes
+///
+/// # Safety
+/// Runs on the worker thread, not the thread that called
+/// `claude_status_core_fetch_usage_async`. Since GTK widgets may only be
+/// touched from the main thread, implementations must marshal back (e.g. via
+/// `g_idle_add`) rather than touching UI state directly.
+pub type CFetchUsageCallback = extern "C" fn(CResultCode, *mut c_void);
+
+/// Wrapper that asserts a raw pointer may cross the thread boundary into the
+/// async fetch worker thread. Safe here because the pointer is only ever
+/// read/written through `core`'s own synchronization (`Mutex`, `AtomicBool`)
+/// or handed back to the caller untouched as `user_data`.
+struct SendPtr<T>(*mut T);
+unsafe impl<T> Send for SendPtr<T> {}
+
 /// Usage data returned to C
 #[repr(C)]
 pub struct CUsageData {
@@ -41,8 +78,13 @@ pub struct CUsageData {
 pub struct CContextInfo {
     /// Context usage percentage (0-100)
     pub context_pct: f64,
-    /// Number of tokens used
+    /// Input + cache tokens from the most recent assistant turn
     pub context_tokens: i64,
+    /// Output tokens from the most recent assistant turn
+    pub output_tokens: i64,
+    /// Running total of input + cache-creation + output tokens across every
+    /// turn in the transcript (cache reads are not counted)
+    pub cumulative_tokens: i64,
     /// Context window size
     pub context_window_size: i64,
     /// Model name (owned by Rust, valid until next call)
@@ -60,6 +102,28 @@ pub struct CCredentialsInfo {
     pub valid: bool,
 }
 
+/// A single polled history sample returned to C
+#[repr(C)]
+pub struct CHistorySample {
+    /// Unix timestamp, seconds
+    pub timestamp: i64,
+    pub five_hour_utilization: f64,
+    pub seven_day_utilization: f64,
+    pub context_pct: f64,
+    pub context_tokens: i64,
+}
+
+/// Detected Claude Code process/resource state returned to C
+#[repr(C)]
+pub struct CSessionInfo {
+    /// Whether at least one Claude Code session is running
+    pub active: bool,
+    /// Number of detected Claude Code processes
+    pub session_count: i32,
+    /// Summed resident memory across all detected processes
+    pub rss_bytes: i64,
+}
+
 /// Result codes
 #[repr(C)]
 pub enum CResultCode {
@@ -86,11 +150,16 @@ thread_local! {
 pub extern "C" fn claude_status_core_new() -> *mut ClaudeStatusCore {
     let core = Box::new(ClaudeStatusCore {
         credentials: None,
+        credentials_path: None,
         config: Config::default(),
         monitor: None,
-        last_usage: None,
+        last_usage: Arc::new(Mutex::new(None)),
         last_context: None,
-        creds_changed: Arc::new(Mutex::new(false)),
+        creds_changed: Arc::new(AtomicBool::new(false)),
+        transcript_changed: Arc::new(AtomicBool::new(false)),
+        usage_ready: Arc::new(AtomicBool::new(false)),
+        history: None,
+        last_error: Arc::new(Mutex::new(None)),
     });
     Box::into_raw(core)
 }
@@ -132,9 +201,12 @@ pub unsafe extern "C" fn claude_status_core_load_credentials(
     match crate::credentials::load_credentials(path_str.as_deref()) {
         Ok(creds) => {
             core.credentials = Some(creds);
+            core.credentials_path = path_str;
+            *core.last_error.lock().unwrap() = None;
             CResultCode::Ok
         }
-        Err(_) => {
+        Err(e) => {
+            set_last_error(&core.last_error, &e);
             core.credentials = None;
             CResultCode::NoCredentials
         }
@@ -143,8 +215,15 @@ pub unsafe extern "C" fn claude_status_core_load_credentials(
 
 /// Get credentials info
 ///
+/// Retained for existing C callers, but the returned `plan_name` is cached in
+/// a `thread_local!` `CString` keyed by the calling thread, so it's only
+/// valid until the next call to this function *on that same thread*. Prefer
+/// `claude_status_core_snapshot`, which copies `plan_name` into a
+/// caller-owned fixed buffer with no such lifetime hazard.
+///
 /// # Safety
 /// `core` must be valid
+#[deprecated(note = "use claude_status_core_snapshot instead")]
 #[no_mangle]
 pub unsafe extern "C" fn claude_status_core_get_credentials_info(
     core: *const ClaudeStatusCore,
@@ -184,6 +263,11 @@ pub unsafe extern "C" fn claude_status_core_get_credentials_info(
 
 /// Fetch usage data from the API (blocking)
 ///
+/// If the stored access token is expired (or close to it), this first
+/// refreshes it via the stored refresh token and writes the new token back
+/// to the credentials file before fetching. A refresh failure is reported as
+/// `AuthError` so the panel can prompt the user to re-login.
+///
 /// # Safety
 /// `core` must be valid
 #[no_mangle]
@@ -195,22 +279,133 @@ pub unsafe extern "C" fn claude_status_core_fetch_usage(
         None => return CResultCode::InvalidCredentials,
     };
 
-    let token = match &core.credentials {
-        Some(c) => &c.access_token,
+    let credentials_path = core.credentials_path.clone();
+    let creds = match &mut core.credentials {
+        Some(c) => c,
         None => return CResultCode::NoCredentials,
     };
 
-    match crate::api::fetch_usage(token) {
+    if crate::credentials::is_expired(creds) {
+        if let Err(e) = crate::credentials::refresh_access_token(creds, credentials_path.as_deref())
+        {
+            set_last_error(&core.last_error, &e);
+            return CResultCode::AuthError;
+        }
+    }
+
+    match crate::api::fetch_usage(&core.credentials.as_ref().unwrap().access_token) {
         Ok(usage) => {
-            core.last_usage = Some(usage);
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_usage(&usage);
+            *core.last_usage.lock().unwrap() = Some(usage);
+            *core.last_error.lock().unwrap() = None;
             CResultCode::Ok
         }
-        Err(crate::api::ApiError::AuthError) => CResultCode::AuthError,
-        Err(crate::api::ApiError::NetworkError(_)) => CResultCode::NetworkError,
-        Err(crate::api::ApiError::ParseError(_)) => CResultCode::ParseError,
+        Err(e) => {
+            let code = match e {
+                crate::api::ApiError::AuthError => CResultCode::AuthError,
+                crate::api::ApiError::NetworkError(_) => CResultCode::NetworkError,
+                crate::api::ApiError::ParseError(_) => CResultCode::ParseError,
+            };
+            set_last_error(&core.last_error, &e);
+            code
+        }
     }
 }
 
+/// Fetch usage data from the API without blocking the calling thread
+///
+/// If the stored access token is expired (or close to it), this first
+/// refreshes it synchronously - same as `claude_status_core_fetch_usage` -
+/// before spawning the worker thread, so a refresh failure is reported as
+/// `AuthError` immediately rather than silently skipped. This briefly blocks
+/// the calling thread only on that (rare) refresh round-trip; the actual
+/// usage fetch, which runs on every call, always happens on the worker
+/// thread. Once the token is valid, clones it, spawns a worker thread to
+/// perform the network round-trip, and invokes `callback(result_code,
+/// user_data)`. On success, stores the result into `last_usage` and sets the
+/// `usage_ready` flag; on failure, `last_usage`/`usage_ready` are left
+/// untouched and the error is recorded for `claude_status_core_last_error`.
+///
+/// # Safety
+/// `core` must be valid. `callback` runs on the worker thread, not the
+/// calling thread - since GTK widgets may only be touched from the main
+/// thread, the callback must marshal back via `g_idle_add` (or similar)
+/// rather than touching UI state directly.
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_fetch_usage_async(
+    core: *mut ClaudeStatusCore,
+    callback: CFetchUsageCallback,
+    user_data: *mut c_void,
+) -> CResultCode {
+    let core = match core.as_mut() {
+        Some(c) => c,
+        None => return CResultCode::InvalidCredentials,
+    };
+
+    let credentials_path = core.credentials_path.clone();
+    let creds = match &mut core.credentials {
+        Some(c) => c,
+        None => return CResultCode::NoCredentials,
+    };
+
+    if crate::credentials::is_expired(creds) {
+        if let Err(e) = crate::credentials::refresh_access_token(creds, credentials_path.as_deref())
+        {
+            set_last_error(&core.last_error, &e);
+            return CResultCode::AuthError;
+        }
+    }
+
+    let token = core.credentials.as_ref().unwrap().access_token.clone();
+
+    let last_usage = Arc::clone(&core.last_usage);
+    let usage_ready = Arc::clone(&core.usage_ready);
+    let last_error = Arc::clone(&core.last_error);
+    let user_data = SendPtr(user_data);
+
+    thread::spawn(move || {
+        let user_data = user_data;
+        let code = match crate::api::fetch_usage(&token) {
+            Ok(usage) => {
+                #[cfg(feature = "metrics")]
+                crate::metrics::record_usage(&usage);
+                *last_usage.lock().unwrap() = Some(usage);
+                *last_error.lock().unwrap() = None;
+                usage_ready.store(true, Ordering::SeqCst);
+                CResultCode::Ok
+            }
+            Err(e) => {
+                let code = match e {
+                    crate::api::ApiError::AuthError => CResultCode::AuthError,
+                    crate::api::ApiError::NetworkError(_) => CResultCode::NetworkError,
+                    crate::api::ApiError::ParseError(_) => CResultCode::ParseError,
+                };
+                set_last_error(&last_error, &e);
+                code
+            }
+        };
+        callback(code, user_data.0);
+    });
+
+    CResultCode::Ok
+}
+
+/// Check if a background `claude_status_core_fetch_usage_async` call has
+/// written a new result since last check. Resets the flag after checking.
+///
+/// # Safety
+/// `core` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_usage_ready(core: *mut ClaudeStatusCore) -> bool {
+    let core = match core.as_mut() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    core.usage_ready.swap(false, Ordering::SeqCst)
+}
+
 /// Get the last fetched usage data
 ///
 /// # Safety
@@ -230,7 +425,7 @@ pub unsafe extern "C" fn claude_status_core_get_usage(core: *const ClaudeStatusC
         }
     };
 
-    match &core.last_usage {
+    match &*core.last_usage.lock().unwrap() {
         Some(usage) => CUsageData {
             five_hour_pct: usage.five_hour.utilization,
             seven_day_pct: usage.seven_day.utilization,
@@ -263,20 +458,77 @@ pub unsafe extern "C" fn claude_status_core_read_context(
 
     match crate::transcript::read_context() {
         Ok(info) => {
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_context(&info);
             core.last_context = Some(info);
+            *core.last_error.lock().unwrap() = None;
             CResultCode::Ok
         }
-        Err(_) => {
+        Err(e) => {
+            set_last_error(&core.last_error, &e);
             core.last_context = None;
             CResultCode::ParseError
         }
     }
 }
 
+/// Get the `Display` message of the last error from `load_credentials`,
+/// `fetch_usage` (sync or async), or `read_context`. Returns null if the last
+/// call of that kind succeeded.
+///
+/// # Safety
+/// `core` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_last_error(
+    core: *const ClaudeStatusCore,
+) -> *const c_char {
+    let core = match core.as_ref() {
+        Some(c) => c,
+        None => return ptr::null(),
+    };
+
+    core.last_error
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map_or(ptr::null(), |e| e.as_ptr())
+}
+
+/// Start the Prometheus metrics exporter, serving `/metrics` on `addr` (or
+/// the default `127.0.0.1:9185` if null). Only compiled in and exported when
+/// the `metrics` feature is enabled.
+///
+/// # Safety
+/// `addr` must be a valid C string or null
+#[cfg(feature = "metrics")]
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_start_metrics(addr: *const c_char) -> CResultCode {
+    let addr_str = if addr.is_null() {
+        crate::metrics::DEFAULT_METRICS_ADDR.to_string()
+    } else {
+        match CStr::from_ptr(addr).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return CResultCode::InvalidCredentials,
+        }
+    };
+
+    match crate::metrics::install(&addr_str) {
+        Ok(()) => CResultCode::Ok,
+        Err(_) => CResultCode::NetworkError,
+    }
+}
+
 /// Get the last read context info
 ///
+/// Retained for existing C callers, but the returned `model_name` is cached
+/// in a `thread_local!` `CString` keyed by the calling thread, so it's only
+/// valid until the next call to this function *on that same thread*. Prefer
+/// `claude_status_core_snapshot`, which copies `model_name` into a
+/// caller-owned fixed buffer with no such lifetime hazard.
+///
 /// # Safety
 /// `core` must be valid
+#[deprecated(note = "use claude_status_core_snapshot instead")]
 #[no_mangle]
 pub unsafe extern "C" fn claude_status_core_get_context(
     core: *const ClaudeStatusCore,
@@ -287,6 +539,8 @@ pub unsafe extern "C" fn claude_status_core_get_context(
             return CContextInfo {
                 context_pct: 0.0,
                 context_tokens: 0,
+                output_tokens: 0,
+                cumulative_tokens: 0,
                 context_window_size: 0,
                 model_name: ptr::null(),
                 valid: false,
@@ -308,6 +562,8 @@ pub unsafe extern "C" fn claude_status_core_get_context(
             CContextInfo {
                 context_pct: info.context_pct,
                 context_tokens: info.context_tokens,
+                output_tokens: info.output_tokens,
+                cumulative_tokens: info.cumulative_tokens,
                 context_window_size: info.context_window_size,
                 model_name: model_ptr.unwrap_or(ptr::null()),
                 valid: true,
@@ -316,6 +572,8 @@ pub unsafe extern "C" fn claude_status_core_get_context(
         None => CContextInfo {
             context_pct: 0.0,
             context_tokens: 0,
+            output_tokens: 0,
+            cumulative_tokens: 0,
             context_window_size: 0,
             model_name: ptr::null(),
             valid: false,
@@ -323,7 +581,8 @@ pub unsafe extern "C" fn claude_status_core_get_context(
     }
 }
 
-/// Start monitoring the credentials file for changes
+/// Start monitoring the credentials file and the `~/.claude/projects`
+/// transcript tree for changes
 ///
 /// # Safety
 /// `core` must be valid, `path` must be a valid C string or null for default
@@ -349,8 +608,9 @@ pub unsafe extern "C" fn claude_status_core_start_monitor(
     // Stop existing monitor
     core.monitor = None;
 
-    let changed_flag = Arc::clone(&core.creds_changed);
-    match crate::monitor::CredentialsMonitor::new(path_str.as_deref(), changed_flag) {
+    let creds_flag = Arc::clone(&core.creds_changed);
+    let transcript_flag = Arc::clone(&core.transcript_changed);
+    match StatusMonitor::new(path_str.as_deref(), creds_flag, transcript_flag) {
         Ok(monitor) => {
             core.monitor = Some(monitor);
             CResultCode::Ok
@@ -359,7 +619,7 @@ pub unsafe extern "C" fn claude_status_core_start_monitor(
     }
 }
 
-/// Stop monitoring the credentials file
+/// Stop monitoring the credentials file and transcript tree
 ///
 /// # Safety
 /// `core` must be valid
@@ -370,7 +630,7 @@ pub unsafe extern "C" fn claude_status_core_stop_monitor(core: *mut ClaudeStatus
     }
 }
 
-/// Check if credentials file has changed since last check
+/// Check if the credentials file has changed since last check
 /// Resets the flag after checking
 ///
 /// # Safety
@@ -384,10 +644,57 @@ pub unsafe extern "C" fn claude_status_core_credentials_changed(
         None => return false,
     };
 
-    let mut changed = core.creds_changed.lock().unwrap();
-    let result = *changed;
-    *changed = false;
-    result
+    core.creds_changed.swap(false, Ordering::SeqCst)
+}
+
+/// Check if the transcript tree has changed since last check
+/// Resets the flag after checking
+///
+/// # Safety
+/// `core` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_transcript_changed(
+    core: *mut ClaudeStatusCore,
+) -> bool {
+    let core = match core.as_mut() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    core.transcript_changed.swap(false, Ordering::SeqCst)
+}
+
+/// Register a callback to be invoked immediately on the watcher thread when
+/// the credentials file changes, instead of (or in addition to) polling
+/// `claude_status_core_credentials_changed`. Pass `None` to clear a
+/// previously registered callback. Requires a monitor already started via
+/// `claude_status_core_start_monitor`; the existing polled flag keeps working
+/// either way.
+///
+/// # Safety
+/// `core` must be valid. `callback`, if present, runs on the watcher thread,
+/// not the calling thread - since GTK widgets may only be touched from the
+/// main thread, implementations must marshal back (e.g. via `g_idle_add`)
+/// rather than touching UI state directly, as with
+/// `claude_status_core_fetch_usage_async`.
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_set_creds_callback(
+    core: *mut ClaudeStatusCore,
+    callback: Option<CredsChangeCallback>,
+    user_data: *mut c_void,
+) -> CResultCode {
+    let core = match core.as_mut() {
+        Some(c) => c,
+        None => return CResultCode::InvalidCredentials,
+    };
+
+    let monitor = match &core.monitor {
+        Some(m) => m,
+        None => return CResultCode::InvalidCredentials,
+    };
+
+    monitor.set_creds_callback(callback.map(|cb| (cb, user_data)));
+    CResultCode::Ok
 }
 
 /// Set configuration value: update interval in seconds
@@ -457,24 +764,314 @@ pub unsafe extern "C" fn claude_status_core_get_color(
     pct: f64,
 ) -> *const c_char {
     static GREEN: &[u8] = b"#5faf5f\0";
-    static YELLOW: &[u8] = b"#d7af5f\0";
-    static ORANGE: &[u8] = b"#d78700\0";
-    static RED: &[u8] = b"#d75f5f\0";
 
     let core = match core.as_ref() {
         Some(c) => c,
         None => return GREEN.as_ptr() as *const c_char,
     };
 
-    let color = if pct < core.config.yellow_threshold as f64 {
-        GREEN
-    } else if pct < core.config.orange_threshold as f64 {
-        YELLOW
-    } else if pct < core.config.red_threshold as f64 {
-        ORANGE
+    color_for_pct(&core.config, pct).as_ptr() as *const c_char
+}
+
+/// Nul-terminated color string for `pct` against `config`'s thresholds
+fn color_for_pct(config: &Config, pct: f64) -> &'static [u8] {
+    if pct < config.yellow_threshold as f64 {
+        b"#5faf5f\0"
+    } else if pct < config.orange_threshold as f64 {
+        b"#d7af5f\0"
+    } else if pct < config.red_threshold as f64 {
+        b"#d78700\0"
     } else {
-        RED
+        b"#d75f5f\0"
+    }
+}
+
+/// Fixed capacities for the inline string fields in [`CStateSnapshot`]
+const SNAPSHOT_MODEL_NAME_LEN: usize = 64;
+const SNAPSHOT_PLAN_NAME_LEN: usize = 16;
+const SNAPSHOT_COLOR_LEN: usize = 8;
+
+/// A single serialized snapshot of usage, context, credentials, and the
+/// computed panel color, written into a caller-owned buffer by
+/// `claude_status_core_snapshot`. Inline fixed-size, nul-terminated char
+/// arrays replace the `thread_local!` `CString` accessors so the result has
+/// no Rust-side string lifetime tied to the calling thread.
+#[repr(C)]
+pub struct CStateSnapshot {
+    pub five_hour_pct: f64,
+    pub seven_day_pct: f64,
+    pub five_hour_reset_ts: i64,
+    pub seven_day_reset_ts: i64,
+    pub usage_valid: bool,
+
+    pub context_pct: f64,
+    pub context_tokens: i64,
+    pub output_tokens: i64,
+    pub cumulative_tokens: i64,
+    pub context_window_size: i64,
+    pub model_name: [c_char; SNAPSHOT_MODEL_NAME_LEN],
+    pub context_valid: bool,
+
+    pub plan_name: [c_char; SNAPSHOT_PLAN_NAME_LEN],
+    pub credentials_valid: bool,
+
+    /// Nul-terminated hex color string, e.g. "#5faf5f"
+    pub color: [c_char; SNAPSHOT_COLOR_LEN],
+}
+
+/// Copy up to `N - 1` bytes of `s` into `buf`, nul-terminated, truncating
+/// silently if `s` doesn't fit
+fn copy_into_c_buf<const N: usize>(s: &str, buf: &mut [c_char; N]) {
+    let bytes = s.as_bytes();
+    let copy_len = bytes.len().min(N - 1);
+    for (i, &b) in bytes[..copy_len].iter().enumerate() {
+        buf[i] = b as c_char;
+    }
+    buf[copy_len] = 0;
+}
+
+/// Serialize the core's current state into `out` (capacity `len` bytes)
+///
+/// Returns the number of bytes written (`size_of::<CStateSnapshot>()`), or 0
+/// if `len` is too small or `core` is invalid.
+///
+/// # Safety
+/// `core` must be valid, `out` must point to at least `len` writable bytes
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_snapshot(
+    core: *const ClaudeStatusCore,
+    out: *mut u8,
+    len: usize,
+) -> usize {
+    let size = std::mem::size_of::<CStateSnapshot>();
+    if len < size {
+        return 0;
+    }
+
+    let core = match core.as_ref() {
+        Some(c) => c,
+        None => return 0,
+    };
+
+    let mut snapshot = CStateSnapshot {
+        five_hour_pct: 0.0,
+        seven_day_pct: 0.0,
+        five_hour_reset_ts: 0,
+        seven_day_reset_ts: 0,
+        usage_valid: false,
+        context_pct: 0.0,
+        context_tokens: 0,
+        output_tokens: 0,
+        cumulative_tokens: 0,
+        context_window_size: 0,
+        model_name: [0; SNAPSHOT_MODEL_NAME_LEN],
+        context_valid: false,
+        plan_name: [0; SNAPSHOT_PLAN_NAME_LEN],
+        credentials_valid: false,
+        color: [0; SNAPSHOT_COLOR_LEN],
+    };
+
+    if let Some(usage) = core.last_usage.lock().unwrap().as_ref() {
+        snapshot.five_hour_pct = usage.five_hour.utilization;
+        snapshot.seven_day_pct = usage.seven_day.utilization;
+        snapshot.five_hour_reset_ts = usage.five_hour.resets_at.timestamp();
+        snapshot.seven_day_reset_ts = usage.seven_day.resets_at.timestamp();
+        snapshot.usage_valid = true;
+    }
+
+    if let Some(context) = &core.last_context {
+        snapshot.context_pct = context.context_pct;
+        snapshot.context_tokens = context.context_tokens;
+        snapshot.output_tokens = context.output_tokens;
+        snapshot.cumulative_tokens = context.cumulative_tokens;
+        snapshot.context_window_size = context.context_window_size;
+        if let Some(model_name) = &context.model_name {
+            copy_into_c_buf(model_name, &mut snapshot.model_name);
+        }
+        snapshot.context_valid = true;
+    }
+
+    if let Some(creds) = &core.credentials {
+        if let Some(plan_name) = &creds.plan_name {
+            copy_into_c_buf(plan_name, &mut snapshot.plan_name);
+        }
+        snapshot.credentials_valid = true;
+    }
+
+    let color = color_for_pct(
+        &core.config,
+        snapshot
+            .five_hour_pct
+            .max(snapshot.seven_day_pct)
+            .max(snapshot.context_pct),
+    );
+    let color_str = std::str::from_utf8(&color[..color.len() - 1]).unwrap_or("#5faf5f");
+    copy_into_c_buf(color_str, &mut snapshot.color);
+
+    std::ptr::write_unaligned(out as *mut CStateSnapshot, snapshot);
+
+    size
+}
+
+/// Open (or create) the history database, using the default path and
+/// retention window if `path` is null / `retention_days` is 0
+///
+/// # Safety
+/// `core` must be valid, `path` must be a valid C string or null
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_start_history(
+    core: *mut ClaudeStatusCore,
+    path: *const c_char,
+    retention_days: i32,
+) -> CResultCode {
+    let core = match core.as_mut() {
+        Some(c) => c,
+        None => return CResultCode::InvalidCredentials,
+    };
+
+    let path_str = if path.is_null() {
+        None
+    } else {
+        match CStr::from_ptr(path).to_str() {
+            Ok(s) => Some(s.to_string()),
+            Err(_) => return CResultCode::InvalidCredentials,
+        }
+    };
+
+    let retention_days = if retention_days > 0 {
+        retention_days as i64
+    } else {
+        crate::history::DEFAULT_RETENTION_DAYS
+    };
+
+    match History::open(path_str.as_deref(), retention_days) {
+        Ok(history) => {
+            core.history = Some(history);
+            CResultCode::Ok
+        }
+        Err(_) => CResultCode::ParseError,
+    }
+}
+
+/// Append the current `last_usage`/`last_context` as one history sample
+///
+/// # Safety
+/// `core` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_record_sample(core: *mut ClaudeStatusCore) -> CResultCode {
+    let core = match core.as_mut() {
+        Some(c) => c,
+        None => return CResultCode::InvalidCredentials,
+    };
+
+    let history = match &core.history {
+        Some(h) => h,
+        None => return CResultCode::InvalidCredentials,
+    };
+
+    let last_usage = core.last_usage.lock().unwrap();
+    let sample = Sample {
+        timestamp: chrono::Utc::now().timestamp(),
+        five_hour_utilization: last_usage.as_ref().map_or(0.0, |u| u.five_hour.utilization),
+        seven_day_utilization: last_usage.as_ref().map_or(0.0, |u| u.seven_day.utilization),
+        context_pct: core.last_context.as_ref().map_or(0.0, |c| c.context_pct),
+        context_tokens: core.last_context.as_ref().map_or(0, |c| c.context_tokens),
+    };
+
+    match history.record(&sample) {
+        Ok(()) => CResultCode::Ok,
+        Err(_) => CResultCode::ParseError,
+    }
+}
+
+/// Fill `out` (capacity `max_len`) with up to `max_len` of the most recent
+/// history samples, oldest first, and return the number written
+///
+/// # Safety
+/// `core` must be valid, `out` must point to at least `max_len` writable
+/// `CHistorySample` slots
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_get_recent_history(
+    core: *const ClaudeStatusCore,
+    out: *mut CHistorySample,
+    max_len: usize,
+) -> usize {
+    let core = match core.as_ref() {
+        Some(c) => c,
+        None => return 0,
     };
 
-    color.as_ptr() as *const c_char
+    let history = match &core.history {
+        Some(h) => h,
+        None => return 0,
+    };
+
+    let samples = match history.recent(max_len as i64) {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    for (i, sample) in samples.iter().enumerate() {
+        *out.add(i) = CHistorySample {
+            timestamp: sample.timestamp,
+            five_hour_utilization: sample.five_hour_utilization,
+            seven_day_utilization: sample.seven_day_utilization,
+            context_pct: sample.context_pct,
+            context_tokens: sample.context_tokens,
+        };
+    }
+
+    samples.len()
+}
+
+/// Whether the seven-day burn rate over the last 20 samples projects hitting
+/// 100% before the period's reset time
+///
+/// # Safety
+/// `core` must be valid
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_seven_day_burn_warning(
+    core: *const ClaudeStatusCore,
+) -> bool {
+    let core = match core.as_ref() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    let Some(history) = &core.history else {
+        return false;
+    };
+    let last_usage = core.last_usage.lock().unwrap();
+    let Some(usage) = last_usage.as_ref() else {
+        return false;
+    };
+
+    let samples = match history.recent(20) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+
+    crate::history::projects_exceeding_before_reset(
+        &samples,
+        |s| s.seven_day_utilization,
+        chrono::Utc::now().timestamp(),
+        usage.seven_day.resets_at.timestamp(),
+    )
+}
+
+/// Scan local processes for running Claude Code sessions
+///
+/// # Safety
+/// `core` may be null; passing a valid `core` is not required since this
+/// doesn't read or write core state
+#[no_mangle]
+pub unsafe extern "C" fn claude_status_core_scan_sessions(
+    _core: *const ClaudeStatusCore,
+) -> CSessionInfo {
+    let info = crate::sessions::scan_sessions();
+    CSessionInfo {
+        active: info.active,
+        session_count: info.session_count,
+        rss_bytes: info.rss_bytes,
+    }
 }
@@ -0,0 +1,220 @@
+//! Local time-series history of polled usage/context samples
+//!
+//! The panel only ever showed the current utilization snapshot; this keeps
+//! every polled sample in a small SQLite database under `~/.claude` so the
+//! tooltip can render a trend ("+12% in last hour") instead of just a point
+//! reading, and so a burn rate can be projected against the reset time.
+
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+    #[error("Database error: {0}")]
+    DbError(#[from] rusqlite::Error),
+}
+
+/// Default history database path, `~/.claude/status-history.db`
+const DEFAULT_HISTORY_DB: &str = ".claude/status-history.db";
+
+/// Default retention window, in days
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// One polled sample of usage and context state
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    /// Unix timestamp, seconds
+    pub timestamp: i64,
+    pub five_hour_utilization: f64,
+    pub seven_day_utilization: f64,
+    pub context_pct: f64,
+    pub context_tokens: i64,
+}
+
+/// Get the default history database path
+pub fn default_history_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(DEFAULT_HISTORY_DB))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_HISTORY_DB))
+}
+
+/// SQLite-backed store of polled samples
+pub struct History {
+    conn: Connection,
+    retention_days: i64,
+}
+
+impl History {
+    /// Open (creating if needed) the history database at `path`, or the
+    /// default path if `path` is `None`
+    pub fn open(path: Option<&str>, retention_days: i64) -> Result<Self, HistoryError> {
+        let path = match path {
+            Some(p) => PathBuf::from(p),
+            None => default_history_path(),
+        };
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS samples (
+                timestamp              INTEGER NOT NULL,
+                five_hour_utilization  REAL NOT NULL,
+                seven_day_utilization  REAL NOT NULL,
+                context_pct            REAL NOT NULL,
+                context_tokens         INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(History {
+            conn,
+            retention_days,
+        })
+    }
+
+    /// Append a sample and prune rows older than the retention window
+    pub fn record(&self, sample: &Sample) -> Result<(), HistoryError> {
+        self.conn.execute(
+            "INSERT INTO samples
+                (timestamp, five_hour_utilization, seven_day_utilization, context_pct, context_tokens)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                sample.timestamp,
+                sample.five_hour_utilization,
+                sample.seven_day_utilization,
+                sample.context_pct,
+                sample.context_tokens,
+            ],
+        )?;
+        self.prune(sample.timestamp)
+    }
+
+    fn prune(&self, now: i64) -> Result<(), HistoryError> {
+        let cutoff = now - self.retention_days * 86_400;
+        self.conn
+            .execute("DELETE FROM samples WHERE timestamp < ?1", params![cutoff])?;
+        Ok(())
+    }
+
+    /// Return the last `limit` samples, oldest first
+    pub fn recent(&self, limit: i64) -> Result<Vec<Sample>, HistoryError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, five_hour_utilization, seven_day_utilization, context_pct, context_tokens
+             FROM samples ORDER BY timestamp DESC LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map(params![limit], |row| {
+            Ok(Sample {
+                timestamp: row.get(0)?,
+                five_hour_utilization: row.get(1)?,
+                seven_day_utilization: row.get(2)?,
+                context_pct: row.get(3)?,
+                context_tokens: row.get(4)?,
+            })
+        })?;
+
+        let mut samples = Vec::new();
+        for row in rows {
+            samples.push(row?);
+        }
+        samples.reverse();
+        Ok(samples)
+    }
+}
+
+/// Percentage points per hour gained between the oldest and newest sample in
+/// `samples`, selected with `select`. `None` if fewer than two samples or the
+/// window spans zero time.
+pub fn burn_rate_per_hour(samples: &[Sample], select: impl Fn(&Sample) -> f64) -> Option<f64> {
+    let first = samples.first()?;
+    let last = samples.last()?;
+    let hours = (last.timestamp - first.timestamp) as f64 / 3600.0;
+    if hours <= 0.0 {
+        return None;
+    }
+    Some((select(last) - select(first)) / hours)
+}
+
+/// Whether the current burn rate projects crossing 100% before `resets_at`
+pub fn projects_exceeding_before_reset(
+    samples: &[Sample],
+    select: impl Fn(&Sample) -> f64,
+    now: i64,
+    resets_at: i64,
+) -> bool {
+    let Some(rate_per_hour) = burn_rate_per_hour(samples, &select) else {
+        return false;
+    };
+    if rate_per_hour <= 0.0 {
+        return false;
+    }
+    let Some(current) = samples.last().map(&select) else {
+        return false;
+    };
+    let hours_until_full = (100.0 - current) / rate_per_hour;
+    let hours_until_reset = (resets_at - now) as f64 / 3600.0;
+    hours_until_full < hours_until_reset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, seven_day_utilization: f64) -> Sample {
+        Sample {
+            timestamp,
+            five_hour_utilization: 0.0,
+            seven_day_utilization,
+            context_pct: 0.0,
+            context_tokens: 0,
+        }
+    }
+
+    #[test]
+    fn test_burn_rate_per_hour_basic() {
+        let samples = vec![sample(0, 10.0), sample(3600, 20.0)];
+        assert_eq!(
+            burn_rate_per_hour(&samples, |s| s.seven_day_utilization),
+            Some(10.0)
+        );
+    }
+
+    #[test]
+    fn test_burn_rate_per_hour_zero_span_is_none() {
+        let samples = vec![sample(1_000, 10.0), sample(1_000, 20.0)];
+        assert_eq!(burn_rate_per_hour(&samples, |s| s.seven_day_utilization), None);
+    }
+
+    #[test]
+    fn test_burn_rate_per_hour_fewer_than_two_samples_is_none() {
+        let samples = vec![sample(0, 10.0)];
+        assert_eq!(burn_rate_per_hour(&samples, |s| s.seven_day_utilization), None);
+    }
+
+    #[test]
+    fn test_projects_exceeding_before_reset_true_when_on_pace() {
+        // 10 pts/hour, currently at 50%, reset in 6 hours -> crosses 100% in 5h
+        let samples = vec![sample(0, 40.0), sample(3600, 50.0)];
+        assert!(projects_exceeding_before_reset(
+            &samples,
+            |s| s.seven_day_utilization,
+            3600,
+            3600 + 6 * 3600,
+        ));
+    }
+
+    #[test]
+    fn test_projects_exceeding_before_reset_false_for_negative_rate() {
+        let samples = vec![sample(0, 50.0), sample(3600, 40.0)];
+        assert!(!projects_exceeding_before_reset(
+            &samples,
+            |s| s.seven_day_utilization,
+            3600,
+            3600 + 3600,
+        ));
+    }
+}
@@ -3,11 +3,15 @@
 //! This library provides the business logic for the Claude status panel plugin,
 //! exposed via a C FFI for integration with the XFCE panel.
 
-mod credentials;
-mod api;
-mod transcript;
+pub mod credentials;
+pub mod api;
+pub mod transcript;
+pub mod history;
+pub mod sessions;
 mod config;
 mod monitor;
 mod ffi;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 
 pub use ffi::*;
@@ -0,0 +1,62 @@
+//! Prometheus metrics exporter
+//!
+//! Exposes the values produced by `api::fetch_usage` and `transcript::read_context`
+//! as a Prometheus text-exposition endpoint so they can be graphed over time
+//! alongside other dashboards, instead of only driving the instantaneous panel
+//! color. Gated behind the `metrics` feature since most users only need the
+//! panel.
+
+use std::net::SocketAddr;
+use thiserror::Error;
+
+use crate::api::UsageData;
+use crate::transcript::ContextInfo;
+
+#[derive(Debug, Error)]
+pub enum MetricsError {
+    #[error("Invalid metrics listen address: {0}")]
+    InvalidAddr(String),
+    #[error("Failed to install Prometheus exporter: {0}")]
+    InstallError(String),
+}
+
+/// Default address the Prometheus exporter listens on
+pub const DEFAULT_METRICS_ADDR: &str = "127.0.0.1:9185";
+
+/// Install a Prometheus exporter listening on `addr`, serving `/metrics`
+///
+/// Installs a process-global recorder, so this must only be called once per
+/// process. Subsequent `record_usage`/`record_context` calls anywhere in the
+/// process report through this recorder.
+pub fn install(addr: &str) -> Result<(), MetricsError> {
+    let socket_addr: SocketAddr = addr
+        .parse()
+        .map_err(|e: std::net::AddrParseError| MetricsError::InvalidAddr(e.to_string()))?;
+
+    metrics_exporter_prometheus::PrometheusBuilder::new()
+        .with_http_listener(socket_addr)
+        .install()
+        .map_err(|e| MetricsError::InstallError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Record a freshly fetched usage sample as Prometheus gauges
+///
+/// Call this on the same cadence as `Config.update_interval`, right after a
+/// successful `fetch_usage`.
+pub fn record_usage(usage: &UsageData) {
+    metrics::gauge!("claude_five_hour_utilization").set(usage.five_hour.utilization);
+    metrics::gauge!("claude_seven_day_utilization").set(usage.seven_day.utilization);
+    metrics::gauge!("claude_seven_day_resets_at_seconds")
+        .set(usage.seven_day.resets_at.timestamp() as f64);
+}
+
+/// Record a freshly read context sample as Prometheus gauges
+///
+/// Call this on the same cadence as `Config.update_interval`, right after a
+/// successful `read_context`.
+pub fn record_context(context: &ContextInfo) {
+    metrics::gauge!("claude_context_pct").set(context.context_pct);
+    metrics::gauge!("claude_context_tokens").set(context.context_tokens as f64);
+}
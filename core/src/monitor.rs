@@ -1,7 +1,9 @@
-//! File monitoring for credentials changes
+//! File monitoring for credentials and transcript changes
 
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::os::raw::c_void;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver};
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -17,34 +19,72 @@ pub enum MonitorError {
     PathError(String),
 }
 
-pub struct CredentialsMonitor {
+/// C callback invoked on the watcher thread as soon as the credentials file
+/// changes, in addition to (not instead of) flipping the polled
+/// `credentials_changed` flag
+///
+/// # Safety
+/// Runs on the watcher thread, not whichever thread registered it via
+/// `set_creds_callback`. Since GTK widgets may only be touched from the main
+/// thread, implementations must marshal back (e.g. via `g_idle_add`) rather
+/// than touching UI state directly - the same requirement as
+/// `CFetchUsageCallback`.
+pub type CredsChangeCallback = extern "C" fn(*mut c_void);
+
+/// Wrapper that asserts a raw `user_data` pointer may cross into the watcher
+/// thread. Safe here because it's only ever handed back to the callback
+/// untouched.
+struct SendPtr(*mut c_void);
+unsafe impl Send for SendPtr {}
+
+struct CredsCallbackSlot {
+    callback: CredsChangeCallback,
+    user_data: SendPtr,
+}
+
+/// Watches both the credentials file and the `~/.claude/projects` transcript
+/// tree, flipping an atomic flag per source as soon as an event arrives
+/// rather than making callers poll a single combined flag. Callers may also
+/// register a callback via `set_creds_callback` to be notified of
+/// credentials changes immediately instead of polling.
+pub struct StatusMonitor {
     _watcher: RecommendedWatcher,
     _handle: thread::JoinHandle<()>,
+    creds_callback: Arc<Mutex<Option<CredsCallbackSlot>>>,
 }
 
-impl CredentialsMonitor {
-    /// Create a new credentials monitor
+impl StatusMonitor {
+    /// Create a new monitor
     ///
-    /// When the file changes, sets the `changed` flag to true.
-    /// The caller should poll this flag and reset it after handling.
+    /// Watches `credentials_path` (or the default credentials path)
+    /// non-recursively, and `~/.claude/projects` recursively so new
+    /// transcript entries are seen as Claude writes them. When a path under
+    /// the credentials file changes, `credentials_changed` is set; when a
+    /// path under the projects tree changes, `transcript_changed` is set.
+    /// The caller polls and resets these flags (e.g. `AtomicBool::swap`).
     pub fn new(
-        path: Option<&str>,
-        changed: Arc<Mutex<bool>>,
+        credentials_path: Option<&str>,
+        credentials_changed: Arc<AtomicBool>,
+        transcript_changed: Arc<AtomicBool>,
     ) -> Result<Self, MonitorError> {
-        let watch_path = match path {
+        let creds_watch_path = match credentials_path {
             Some(p) => {
-                let p = if let Some(rest) = p.strip_prefix("~/") {
+                if let Some(rest) = p.strip_prefix("~/") {
                     dirs::home_dir()
                         .map(|h| h.join(rest))
                         .unwrap_or_else(|| PathBuf::from(p))
                 } else {
                     PathBuf::from(p)
-                };
-                p
+                }
             }
             None => default_credentials_path(),
         };
 
+        let projects_dir = dirs::home_dir().map(|h| h.join(".claude").join("projects"));
+
+        let creds_callback: Arc<Mutex<Option<CredsCallbackSlot>>> = Arc::new(Mutex::new(None));
+        let creds_callback_for_thread = Arc::clone(&creds_callback);
+
         let (tx, rx): (_, Receiver<Result<Event, notify::Error>>) = channel();
 
         let mut watcher = RecommendedWatcher::new(
@@ -56,29 +96,58 @@ impl CredentialsMonitor {
         .map_err(|e| MonitorError::WatcherError(e.to_string()))?;
 
         watcher
-            .watch(&watch_path, RecursiveMode::NonRecursive)
+            .watch(&creds_watch_path, RecursiveMode::NonRecursive)
             .map_err(|e| MonitorError::PathError(e.to_string()))?;
 
+        if let Some(projects_dir) = &projects_dir {
+            if projects_dir.exists() {
+                watcher
+                    .watch(projects_dir, RecursiveMode::Recursive)
+                    .map_err(|e| MonitorError::PathError(e.to_string()))?;
+            }
+        }
+
         // Spawn thread to process events
         let handle = thread::spawn(move || {
             for res in rx {
                 if let Ok(event) = res {
                     use notify::EventKind::*;
-                    match event.kind {
-                        Create(_) | Modify(_) => {
-                            if let Ok(mut flag) = changed.lock() {
-                                *flag = true;
+                    if !matches!(event.kind, Create(_) | Modify(_)) {
+                        continue;
+                    }
+
+                    for path in &event.paths {
+                        if path.starts_with(&creds_watch_path) {
+                            credentials_changed.store(true, Ordering::SeqCst);
+                            if let Some(slot) = creds_callback_for_thread.lock().unwrap().as_ref() {
+                                (slot.callback)(slot.user_data.0);
                             }
+                        } else if projects_dir
+                            .as_ref()
+                            .is_some_and(|dir| path.starts_with(dir))
+                        {
+                            transcript_changed.store(true, Ordering::SeqCst);
                         }
-                        _ => {}
                     }
                 }
             }
         });
 
-        Ok(CredentialsMonitor {
+        Ok(StatusMonitor {
             _watcher: watcher,
             _handle: handle,
+            creds_callback,
         })
     }
+
+    /// Register a callback to be invoked on the watcher thread whenever the
+    /// credentials file changes, or clear it with `None`. Replaces any
+    /// previously registered callback.
+    pub fn set_creds_callback(&self, callback: Option<(CredsChangeCallback, *mut c_void)>) {
+        let mut slot = self.creds_callback.lock().unwrap();
+        *slot = callback.map(|(callback, user_data)| CredsCallbackSlot {
+            callback,
+            user_data: SendPtr(user_data),
+        });
+    }
 }
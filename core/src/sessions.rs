@@ -0,0 +1,84 @@
+//! Detect running Claude Code sessions via process enumeration
+//!
+//! The core previously only inferred activity from credentials and the
+//! latest transcript file. This gives a direct signal for "a session is
+//! actually running right now," so the panel can tell "idle, no session"
+//! apart from "session running but usage numbers haven't moved yet."
+
+use sysinfo::System;
+
+/// Executable basename (or basename prefix, e.g. `claude-code`) that
+/// identifies the Claude Code CLI
+const PROCESS_MATCH: &str = "claude";
+
+/// Executable basename prefix that identifies this plugin's own process,
+/// excluded from matches so the panel doesn't count itself as a session
+const SELF_MATCH: &str = "claude-status";
+
+/// Resource usage summed across every detected Claude Code process
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionInfo {
+    pub active: bool,
+    pub session_count: i32,
+    pub rss_bytes: i64,
+}
+
+/// Scan local processes for running Claude Code sessions, summing resident
+/// memory (RSS) across every match; CPU usage is not collected
+pub fn scan_sessions() -> SessionInfo {
+    let mut system = System::new_all();
+    system.refresh_all();
+
+    let mut session_count = 0;
+    let mut rss_bytes: i64 = 0;
+
+    for process in system.processes().values() {
+        if !is_claude_process(process) {
+            continue;
+        }
+
+        session_count += 1;
+        rss_bytes += process.memory() as i64;
+    }
+
+    SessionInfo {
+        active: session_count > 0,
+        session_count,
+        rss_bytes,
+    }
+}
+
+/// Lowercased basename of a path-like string (executable path or argv[0]),
+/// e.g. `/usr/local/bin/claude-status-plugin` -> `claude-status-plugin`
+fn basename_lower(path: &str) -> String {
+    path.rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(path)
+        .to_lowercase()
+}
+
+/// Whether `basename` equals `prefix` or is `prefix` followed by a `-`
+/// (e.g. `claude` matches `claude` and `claude-code`, not `claude_notes.md`)
+fn matches_basename(basename: &str, prefix: &str) -> bool {
+    basename == prefix || basename.starts_with(&format!("{prefix}-"))
+}
+
+/// Whether `process` looks like a Claude Code session: its executable
+/// basename, or argv[0]'s basename, matches [`PROCESS_MATCH`] - but it isn't
+/// this plugin's own process. Arbitrary command-line arguments (file paths,
+/// grep patterns, etc.) are never treated as matches, since they'd produce
+/// false positives like `vim claude_notes.md`.
+fn is_claude_process(process: &sysinfo::Process) -> bool {
+    let name = basename_lower(&process.name().to_string_lossy());
+    let argv0 = process
+        .cmd()
+        .first()
+        .map(|arg| basename_lower(&arg.to_string_lossy()))
+        .unwrap_or_default();
+
+    if matches_basename(&name, SELF_MATCH) || matches_basename(&argv0, SELF_MATCH) {
+        return false;
+    }
+
+    matches_basename(&name, PROCESS_MATCH) || matches_basename(&argv0, PROCESS_MATCH)
+}
@@ -19,8 +19,20 @@ pub enum TranscriptError {
 
 #[derive(Debug, Clone)]
 pub struct ContextInfo {
+    /// Current context fill, as a percentage of `context_window_size`
     pub context_pct: f64,
+    /// Input + cache tokens from the most recent assistant turn - this is
+    /// what actually fills the context window
     pub context_tokens: i64,
+    /// Output tokens from the most recent assistant turn
+    pub output_tokens: i64,
+    /// Running total of newly-processed tokens (input + newly-created cache
+    /// + output) across every turn in the transcript, for tracking overall
+    /// session token spend. Deliberately excludes cache *reads*: those
+    /// reflect the same already-cached context being billed again each turn,
+    /// not new spend, and summing them would balloon this far past the
+    /// session's real token usage.
+    pub cumulative_tokens: i64,
     pub context_window_size: i64,
     pub model_name: Option<String>,
 }
@@ -28,6 +40,21 @@ pub struct ContextInfo {
 /// Default context window size (200K tokens)
 const CONTEXT_WINDOW_DEFAULT: i64 = 200_000;
 
+/// Context window size by model name, keyed by substring match against the
+/// lowercased model name. Checked in order, so more specific entries (e.g.
+/// the 1M-context beta) must come before the general-purpose fallbacks.
+const CONTEXT_WINDOW_TABLE: &[(&str, i64)] = &[("[1m]", 1_000_000), ("-1m-", 1_000_000)];
+
+/// Look up the context window size for a model name, falling back to
+/// [`CONTEXT_WINDOW_DEFAULT`] for current Sonnet/Opus-class models
+fn context_window_for_model(model_name: &str) -> i64 {
+    let lower = model_name.to_lowercase();
+    CONTEXT_WINDOW_TABLE
+        .iter()
+        .find(|(needle, _)| lower.contains(needle))
+        .map_or(CONTEXT_WINDOW_DEFAULT, |(_, size)| *size)
+}
+
 #[derive(Debug, Deserialize)]
 struct TranscriptEntry {
     #[serde(rename = "type")]
@@ -44,6 +71,7 @@ struct MessageData {
 #[derive(Debug, Deserialize)]
 struct UsageData {
     input_tokens: Option<i64>,
+    output_tokens: Option<i64>,
     cache_creation_input_tokens: Option<i64>,
     cache_read_input_tokens: Option<i64>,
 }
@@ -101,6 +129,8 @@ pub fn read_context() -> Result<ContextInfo, TranscriptError> {
     let mut last_input: i64 = 0;
     let mut last_cache_creation: i64 = 0;
     let mut last_cache_read: i64 = 0;
+    let mut last_output: i64 = 0;
+    let mut cumulative_tokens: i64 = 0;
     let mut last_model: Option<String> = None;
 
     for line in reader.lines() {
@@ -127,6 +157,10 @@ pub fn read_context() -> Result<ContextInfo, TranscriptError> {
                     last_input = usage.input_tokens.unwrap_or(0);
                     last_cache_creation = usage.cache_creation_input_tokens.unwrap_or(0);
                     last_cache_read = usage.cache_read_input_tokens.unwrap_or(0);
+                    last_output = usage.output_tokens.unwrap_or(0);
+                    // Cache reads are excluded: they re-bill the same cached
+                    // context every turn rather than introducing new tokens.
+                    cumulative_tokens += last_input + last_cache_creation + last_output;
                 }
             }
         }
@@ -134,13 +168,40 @@ pub fn read_context() -> Result<ContextInfo, TranscriptError> {
     }
 
     let total_context = last_input + last_cache_creation + last_cache_read;
-    let context_window = CONTEXT_WINDOW_DEFAULT;
+    let context_window = last_model
+        .as_deref()
+        .map_or(CONTEXT_WINDOW_DEFAULT, context_window_for_model);
     let context_pct = (total_context as f64 / context_window as f64 * 100.0).min(100.0);
 
     Ok(ContextInfo {
         context_pct,
         context_tokens: total_context,
+        output_tokens: last_output,
+        cumulative_tokens,
         context_window_size: context_window,
         model_name: last_model,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_window_for_model_1m_beta() {
+        assert_eq!(context_window_for_model("claude-sonnet-4-5[1m]"), 1_000_000);
+    }
+
+    #[test]
+    fn test_context_window_for_model_default() {
+        assert_eq!(
+            context_window_for_model("claude-opus-4-1"),
+            CONTEXT_WINDOW_DEFAULT
+        );
+    }
+
+    #[test]
+    fn test_context_window_for_model_case_insensitive() {
+        assert_eq!(context_window_for_model("CLAUDE-SONNET-4-5[1M]"), 1_000_000);
+    }
+}